@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Asset-matching spec for a configured tool: a release asset qualifies when its
+/// lowercased name contains every `include` substring, none of the `exclude`
+/// substrings, and ends with `extension`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AssetMatch {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    pub extension: String,
+}
+
+impl AssetMatch {
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.include.iter().all(|s| name.contains(&s.to_lowercase()))
+            && !self.exclude.iter().any(|s| name.contains(&s.to_lowercase()))
+            && name.ends_with(&self.extension.to_lowercase())
+    }
+}
+
+/// One tool entry from a `tools.toml` config file.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ToolConfig {
+    pub name: String,
+    pub repo: String,
+    /// Directory name (relative to the output dir) this tool is installed into.
+    pub dir: String,
+    pub exe: String,
+    pub version_pattern: String,
+    pub asset: AssetMatch,
+    /// Pin this tool to an exact version (e.g. "0.4.18"), overriding `channel`.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Release stream to resolve against when `version` is unset: "stable" (the default)
+    /// or "prerelease".
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Base64-encoded minisign public key trusted for this tool's releases. When set and
+    /// a `.minisig` asset is published, the archive's signature is verified in addition to
+    /// its SHA-256 checksum.
+    #[serde(default)]
+    pub minisign_key: Option<String>,
+}
+
+/// A list of tools to manage, loaded from TOML.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Config {
+    #[serde(rename = "tool")]
+    pub tools: Vec<ToolConfig>,
+}
+
+impl Config {
+    /// The built-in PowerShell + UV definitions, used when no config file is found.
+    pub fn builtin() -> Self {
+        Self {
+            tools: vec![
+                ToolConfig {
+                    name: "PowerShell".to_string(),
+                    repo: "PowerShell/PowerShell".to_string(),
+                    dir: "pwsh".to_string(),
+                    exe: "pwsh.exe".to_string(),
+                    version_pattern: r"PowerShell ([\d\.]+)".to_string(),
+                    asset: AssetMatch {
+                        include: vec!["win".to_string(), "x64".to_string()],
+                        exclude: vec!["symbols".to_string(), "arm".to_string()],
+                        extension: ".zip".to_string(),
+                    },
+                    version: None,
+                    channel: None,
+                    minisign_key: None,
+                },
+                ToolConfig {
+                    name: "UV".to_string(),
+                    repo: "astral-sh/uv".to_string(),
+                    dir: "uv".to_string(),
+                    exe: "uv.exe".to_string(),
+                    version_pattern: r"uv ([\d\.]+)".to_string(),
+                    asset: AssetMatch {
+                        include: vec!["windows".to_string(), "x86_64".to_string()],
+                        exclude: vec![],
+                        extension: ".zip".to_string(),
+                    },
+                    version: None,
+                    channel: None,
+                    minisign_key: None,
+                },
+            ],
+        }
+    }
+
+    /// Loads the tool list from `path` if given, else the default per-user config
+    /// location, falling back to [`Config::builtin`] only when no path was explicitly
+    /// requested. A `path` that was explicitly given but doesn't exist is a hard error —
+    /// silently falling back there would risk updating the wrong tools on a typo'd path.
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(p) = path {
+            if !p.exists() {
+                return Err(format!("config file not found: {}", p.display()).into());
+            }
+            let text = std::fs::read_to_string(p)?;
+            return Ok(toml::from_str(&text)?);
+        }
+
+        match default_config_path() {
+            Some(p) if p.exists() => {
+                let text = std::fs::read_to_string(&p)?;
+                Ok(toml::from_str(&text)?)
+            }
+            _ => Ok(Self::builtin()),
+        }
+    }
+}
+
+/// `<config dir>/uv_setup/tools.toml`, the default config location when `--config` is absent.
+fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("uv_setup").join("tools.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset_match(include: &[&str], exclude: &[&str], extension: &str) -> AssetMatch {
+        AssetMatch {
+            include: include.iter().map(|s| s.to_string()).collect(),
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            extension: extension.to_string(),
+        }
+    }
+
+    #[test]
+    fn matches_requires_every_include_substring() {
+        let m = asset_match(&["win", "x64"], &[], ".zip");
+        assert!(m.matches("pwsh-7.4.5-win-x64.zip"));
+        assert!(!m.matches("pwsh-7.4.5-win-arm64.zip"));
+    }
+
+    #[test]
+    fn matches_rejects_any_exclude_substring() {
+        let m = asset_match(&["win"], &["symbols", "arm"], ".zip");
+        assert!(!m.matches("pwsh-7.4.5-win-arm64.zip"));
+        assert!(!m.matches("pwsh-7.4.5-win-x64-symbols.zip"));
+        assert!(m.matches("pwsh-7.4.5-win-x64.zip"));
+    }
+
+    #[test]
+    fn matches_requires_extension_and_is_case_insensitive() {
+        let m = asset_match(&["windows"], &[], ".zip");
+        assert!(m.matches("UV-WINDOWS-X86_64.ZIP"));
+        assert!(!m.matches("uv-windows-x86_64.tar.gz"));
+    }
+}
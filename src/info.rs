@@ -0,0 +1,44 @@
+use crate::config::ToolConfig;
+use crate::{build_tool, detect_installed_version, needs_update, resolve_release, Args};
+use reqwest::blocking::Client;
+use std::path::PathBuf;
+
+/// Prints, for each configured tool, its detected local version, resolved executable
+/// path, latest available GitHub version, whether an update is pending, and whether its
+/// directory is on `PATH` — all without downloading anything. Respects the same
+/// `--pwsh-version`/`--uv-version`/`--prerelease` flags (and per-tool config pins) that an
+/// actual update run would use, so the reported channel matches what would be installed.
+pub fn run(client: &Client, tools: &[ToolConfig], base_dir: &PathBuf, args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    for tool_config in tools {
+        let dir = base_dir.join(&tool_config.dir);
+        let exe_path = dir.join(&tool_config.exe);
+
+        println!("\n{}", tool_config.name);
+        println!("  Path: {}", exe_path.display());
+
+        let installed = detect_installed_version(&exe_path, &tool_config.version_pattern);
+        match &installed {
+            Some(version) => println!("  Installed version: {}", version),
+            None => println!("  Installed version: not installed"),
+        }
+
+        let tool = build_tool(tool_config, args)?;
+        match resolve_release(client, &tool)
+            .and_then(|r| Ok(semver::Version::parse(r.tag_name.trim_start_matches('v'))?))
+        {
+            Ok(latest) => {
+                println!("  Latest version: {}", latest);
+                let update_pending = needs_update(&tool, installed.as_ref(), &latest);
+                println!("  Update available: {}", if update_pending { "yes" } else { "no" });
+            }
+            Err(e) => println!("  Latest version: unknown ({e})"),
+        }
+
+        let on_path = std::env::var_os("PATH")
+            .map(|paths| std::env::split_paths(&paths).any(|p| p == dir))
+            .unwrap_or(false);
+        println!("  On PATH: {}", if on_path { "yes" } else { "no" });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// The backup directory for a tool's previous install, as a sibling of `tool_dir`
+/// (e.g. `pwsh` -> `pwsh.backup`).
+fn backup_dir_for(tool_dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let name = tool_dir
+        .file_name()
+        .ok_or("tool directory has no name")?
+        .to_string_lossy();
+    Ok(tool_dir.with_file_name(format!("{name}.backup")))
+}
+
+/// Atomically swaps a freshly-extracted install into place: `tool_dir` is renamed to a
+/// sibling `backup/` directory (replacing any prior backup), then `staging` is renamed to
+/// `tool_dir`. Each step is a single directory rename, so a crash at any point leaves
+/// either the old install (in `backup`) or the new one (in `staging` or `tool_dir`) fully
+/// intact — never a half-copied mix of the two.
+pub fn promote(tool_dir: &Path, staging: &TempDir) -> Result<(), Box<dyn std::error::Error>> {
+    let backup_dir = backup_dir_for(tool_dir)?;
+
+    if backup_dir.exists() {
+        std::fs::remove_dir_all(&backup_dir)?;
+    }
+    if tool_dir.exists() {
+        std::fs::rename(tool_dir, &backup_dir)?;
+    }
+
+    // `staging` must be a sibling of `tool_dir`, not nested inside it, so this rename is a
+    // single directory move rather than a per-file copy loop.
+    std::fs::rename(staging.path(), tool_dir)?;
+
+    Ok(())
+}
+
+/// Reverses the last [`promote`]: swaps `tool_dir` and `<name>.backup` via a third, uniquely
+/// named sibling directory so neither rename ever targets a path that still exists, and
+/// a second rollback undoes the first.
+pub fn rollback(tool_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let backup_dir = backup_dir_for(tool_dir)?;
+    if !backup_dir.exists() {
+        return Err(format!("No backup found for {}", tool_dir.display()).into());
+    }
+
+    let name = tool_dir
+        .file_name()
+        .ok_or("tool directory has no name")?
+        .to_string_lossy();
+    let holding_dir = tool_dir.with_file_name(format!("{name}.swap"));
+    if holding_dir.exists() {
+        std::fs::remove_dir_all(&holding_dir)?;
+    }
+
+    std::fs::rename(tool_dir, &holding_dir)?;
+    std::fs::rename(&backup_dir, tool_dir)?;
+    std::fs::rename(&holding_dir, &backup_dir)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(dir: &Path, name: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, name).unwrap();
+        path
+    }
+
+    #[test]
+    fn promote_swaps_staging_in_and_moves_previous_install_to_backup() {
+        let root = tempfile::tempdir().unwrap();
+        let tool_dir = root.path().join("pwsh");
+        std::fs::create_dir(&tool_dir).unwrap();
+        marker(&tool_dir, "old.txt");
+
+        let staging = tempfile::tempdir_in(root.path()).unwrap();
+        marker(staging.path(), "new.txt");
+
+        promote(&tool_dir, &staging).unwrap();
+
+        assert!(tool_dir.join("new.txt").exists());
+        assert!(!tool_dir.join("old.txt").exists());
+        assert!(backup_dir_for(&tool_dir).unwrap().join("old.txt").exists());
+    }
+
+    #[test]
+    fn promote_with_no_prior_install_just_moves_staging_in() {
+        let root = tempfile::tempdir().unwrap();
+        let tool_dir = root.path().join("uv");
+
+        let staging = tempfile::tempdir_in(root.path()).unwrap();
+        marker(staging.path(), "new.txt");
+
+        promote(&tool_dir, &staging).unwrap();
+
+        assert!(tool_dir.join("new.txt").exists());
+        assert!(!backup_dir_for(&tool_dir).unwrap().exists());
+    }
+
+    #[test]
+    fn rollback_restores_previous_install_and_is_itself_reversible() {
+        let root = tempfile::tempdir().unwrap();
+        let tool_dir = root.path().join("pwsh");
+        std::fs::create_dir(&tool_dir).unwrap();
+        marker(&tool_dir, "old.txt");
+
+        let staging = tempfile::tempdir_in(root.path()).unwrap();
+        marker(staging.path(), "new.txt");
+        promote(&tool_dir, &staging).unwrap();
+
+        rollback(&tool_dir).unwrap();
+        assert!(tool_dir.join("old.txt").exists());
+        assert!(!tool_dir.join("new.txt").exists());
+        assert!(backup_dir_for(&tool_dir).unwrap().join("new.txt").exists());
+
+        // A second rollback undoes the first, landing back on the "new" install.
+        rollback(&tool_dir).unwrap();
+        assert!(tool_dir.join("new.txt").exists());
+        assert!(!tool_dir.join("old.txt").exists());
+    }
+
+    #[test]
+    fn rollback_without_a_backup_is_an_error() {
+        let root = tempfile::tempdir().unwrap();
+        let tool_dir = root.path().join("pwsh");
+        std::fs::create_dir(&tool_dir).unwrap();
+
+        assert!(rollback(&tool_dir).is_err());
+    }
+}
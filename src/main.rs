@@ -1,21 +1,67 @@
-use clap::Parser;
-use indicatif::{ProgressBar, ProgressStyle};
+use clap::{Parser, Subcommand};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
 use serde::Deserialize;
-use std::{io::Write, path::PathBuf, process::Command};
+use std::{path::{Path, PathBuf}, process::Command, thread};
 use semver::Version;
 
+mod config;
+mod info;
+mod install;
+mod verify;
+
+use config::ToolConfig;
+
 #[derive(Parser, Debug)]
 #[command(about = "Downloads the latest PowerShell and UV for Windows x64")]
-struct Args {
+pub(crate) struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     #[arg(short, long)]
     output: Option<PathBuf>,
+
+    /// Path to a tools.toml listing the tools to manage, instead of the built-in
+    /// PowerShell + UV definitions
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Install this exact PowerShell version instead of the latest (e.g. "7.4.5")
+    #[arg(long)]
+    pwsh_version: Option<String>,
+
+    /// Install this exact UV version instead of the latest (e.g. "0.4.18")
+    #[arg(long)]
+    uv_version: Option<String>,
+
+    /// Consider prerelease builds when resolving "latest" for tools without a pinned version
+    #[arg(long)]
+    prerelease: bool,
+
+    /// Restore each tool's previously installed version instead of checking for updates
+    #[arg(long)]
+    rollback: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Report installed versions, paths, and update availability without downloading anything
+    Info,
+}
+
+/// Which release stream a tool should be resolved against when no explicit version is pinned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    Stable,
+    Prerelease,
 }
 
 #[derive(Deserialize)]
 struct Release {
     tag_name: String,
+    #[serde(default)]
+    body: String,
     assets: Vec<Asset>,
 }
 
@@ -29,43 +75,92 @@ struct Tool {
     name: String,
     repo: String,
     exe: String,
-    version_pattern: &'static str,
+    version_pattern: String,
+    asset: config::AssetMatch,
+    /// Base64-encoded minisign public key trusted for this tool's releases, if any.
+    /// When set and a `.minisig` asset is published, the archive's signature is
+    /// verified in addition to its SHA-256 checksum.
+    minisign_key: Option<String>,
+    /// Exact version to install, overriding whatever `channel` would otherwise resolve to.
+    explicit_version: Option<Version>,
+    /// Release stream to resolve against when `explicit_version` is unset.
+    channel: Channel,
 }
 
 impl Tool {
-    fn powershell() -> Self {
+    fn from_config(config: &ToolConfig) -> Self {
         Self {
-            name: "PowerShell".to_string(),
-            repo: "PowerShell/PowerShell".to_string(),
-            exe: "pwsh.exe".to_string(),
-            version_pattern: r"PowerShell ([\d\.]+)",
+            name: config.name.clone(),
+            repo: config.repo.clone(),
+            exe: config.exe.clone(),
+            version_pattern: config.version_pattern.clone(),
+            asset: config.asset.clone(),
+            minisign_key: config.minisign_key.clone(),
+            explicit_version: None,
+            channel: Channel::Stable,
         }
     }
 
-    fn uv() -> Self {
-        Self {
-            name: "UV".to_string(),
-            repo: "astral-sh/uv".to_string(),
-            exe: "uv.exe".to_string(),
-            version_pattern: r"uv ([\d\.]+)",
+    /// Pins this tool to an exact version, parsed from a string like "0.4.18" or "v0.4.18".
+    fn with_version(mut self, version: Option<&str>) -> Result<Self, semver::Error> {
+        if let Some(version) = version {
+            self.explicit_version = Some(Version::parse(version.trim_start_matches('v'))?);
         }
+        Ok(self)
+    }
+
+    fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
     }
 
     fn matches_asset(&self, name: &str) -> bool {
-        let name = name.to_lowercase();
-        match self.name.as_str() {
-            "PowerShell" => name.contains("win") && name.contains("x64") && 
-                           name.ends_with(".zip") && !name.contains("symbols") && 
-                           !name.contains("arm"),
-            "UV" => name.contains("windows") && name.contains("x86_64") && 
-                    name.ends_with(".zip"),
-            _ => false,
-        }
+        self.asset.matches(name)
     }
 }
 
-fn create_progress_bar(len: u64, message: &str) -> ProgressBar {
-    let pb = ProgressBar::new(len);
+/// Resolves the release channel for a tool: the global `--prerelease` flag takes
+/// precedence for every tool, otherwise the tool's own config `channel` applies
+/// (defaulting to "stable").
+fn resolve_channel(tool_config: &ToolConfig, args: &Args) -> Result<Channel, Box<dyn std::error::Error>> {
+    if args.prerelease {
+        return Ok(Channel::Prerelease);
+    }
+    match tool_config.channel.as_deref() {
+        None | Some("stable") => Ok(Channel::Stable),
+        Some("prerelease") => Ok(Channel::Prerelease),
+        Some(other) => Err(format!(
+            "tool \"{}\": unknown channel \"{other}\" (expected \"stable\" or \"prerelease\")",
+            tool_config.name
+        )
+        .into()),
+    }
+}
+
+/// Resolves the pinned version for a tool: the built-in `--pwsh-version`/`--uv-version`
+/// flags take precedence for those two tool names, otherwise the tool's own config
+/// `version` applies.
+fn resolve_pinned_version<'a>(tool_config: &'a ToolConfig, args: &'a Args) -> Option<&'a str> {
+    let cli_pin = match tool_config.name.as_str() {
+        "PowerShell" => args.pwsh_version.as_deref(),
+        "UV" => args.uv_version.as_deref(),
+        _ => None,
+    };
+    cli_pin.or(tool_config.version.as_deref())
+}
+
+/// Builds a [`Tool`] from a config entry, applying the version pin and channel that
+/// `args` (CLI flags, falling back to the tool's own config) resolve to.
+fn build_tool(tool_config: &ToolConfig, args: &Args) -> Result<Tool, Box<dyn std::error::Error>> {
+    let channel = resolve_channel(tool_config, args)?;
+    let pinned_version = resolve_pinned_version(tool_config, args);
+    Ok(Tool::from_config(tool_config)
+        .with_version(pinned_version)?
+        .with_channel(channel))
+}
+
+fn create_progress_bar(multi: &MultiProgress, len: u64, message: &str) -> ProgressBar {
+    let pb = multi.add(ProgressBar::new(len));
     pb.set_style(ProgressStyle::default_bar()
         .template("[{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta}) • {msg}")
         .unwrap()
@@ -74,50 +169,146 @@ fn create_progress_bar(len: u64, message: &str) -> ProgressBar {
     pb
 }
 
-fn process_tool(client: &Client, tool: &Tool, dir: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    println!("\nChecking {} installation...", tool.name);
-    
-    // Check current version
-    let exe_path = dir.join(&tool.exe);
-    let current_version = if exe_path.exists() {
-        Command::new(&exe_path)
-            .arg("--version")
-            .output()
-            .ok()
-            .filter(|output| output.status.success())
-            .and_then(|output| {
-                let version_str = String::from_utf8_lossy(&output.stdout);
-                regex::Regex::new(tool.version_pattern)
-                    .ok()?
-                    .captures(&version_str)?
-                    .get(1)?
-                    .as_str()
-                    .parse::<Version>()
-                    .ok()
-            })
+/// Prints a line above the live progress bars instead of through raw stdout, so concurrent
+/// tool updates don't garble each other's bars.
+fn log(multi: &MultiProgress, message: impl AsRef<str>) {
+    let _ = multi.println(message.as_ref());
+}
+
+/// Verifies a downloaded archive against a published SHA-256 checksum (sidecar asset or,
+/// failing that, a hash mentioned in the release body) and, when the tool has a trusted
+/// minisign key and a `.minisig` asset is published, against its detached signature.
+fn verify_asset(
+    client: &Client,
+    tool: &Tool,
+    release: &Release,
+    asset: &Asset,
+    bytes: &[u8],
+    multi: &MultiProgress,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let checksum_asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == format!("{}.sha256", asset.name));
+
+    let expected_hex = if let Some(checksum_asset) = checksum_asset {
+        let text = verify::fetch_text(client, &checksum_asset.browser_download_url)?;
+        verify::extract_hex_digest(&text)
     } else {
-        None
+        verify::find_hex_digest_in_body(&release.body, &asset.name)
     };
 
-    // Get latest version
-    let release: Release = client
-        .get(&format!("https://api.github.com/repos/{}/releases/latest", tool.repo))
-        .send()?
-        .json()?;
-    
-    let latest_version = Version::parse(release.tag_name.trim_start_matches('v'))?;
-    
+    match expected_hex {
+        Some(hex) => {
+            verify::verify_checksum(bytes, &hex)?;
+            log(multi, format!("Checksum verified for {}", asset.name));
+        }
+        None => {
+            log(multi, format!("Warning: no published checksum found for {}, skipping integrity check", asset.name));
+        }
+    }
+
+    if let Some(public_key) = tool.minisign_key.as_deref() {
+        if let Some(sig_asset) = release
+            .assets
+            .iter()
+            .find(|a| a.name == format!("{}.minisig", asset.name))
+        {
+            let signature_text = verify::fetch_text(client, &sig_asset.browser_download_url)?;
+            verify::verify_minisign(bytes, &signature_text, public_key)?;
+            log(multi, format!("Signature verified for {}", asset.name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves the `Release` a tool should be installed from: an exact tagged release when
+/// `explicit_version` is pinned, otherwise the newest release on the tool's `channel`.
+fn resolve_release(client: &Client, tool: &Tool) -> Result<Release, Box<dyn std::error::Error>> {
+    if let Some(version) = &tool.explicit_version {
+        // Tags vary across repos ("v1.2.3" vs "1.2.3"), so rather than guess a prefix,
+        // list all releases and match by parsed semver like the prerelease channel does.
+        let url = format!("https://api.github.com/repos/{}/releases", tool.repo);
+        let releases: Vec<Release> = client.get(&url).send()?.json()?;
+        return releases
+            .into_iter()
+            .find(|r| Version::parse(r.tag_name.trim_start_matches('v')).ok().as_ref() == Some(version))
+            .ok_or_else(|| format!("no release matching version {version} found for {}", tool.repo).into());
+    }
+
+    match tool.channel {
+        Channel::Stable => {
+            let url = format!("https://api.github.com/repos/{}/releases/latest", tool.repo);
+            Ok(client.get(&url).send()?.json()?)
+        }
+        Channel::Prerelease => {
+            let url = format!("https://api.github.com/repos/{}/releases", tool.repo);
+            let releases: Vec<Release> = client.get(&url).send()?.json()?;
+            releases
+                .into_iter()
+                .max_by_key(|r| Version::parse(r.tag_name.trim_start_matches('v')).ok())
+                .ok_or_else(|| "No releases found".into())
+        }
+    }
+}
+
+/// Whether `tool` should be (re)installed given its currently detected version and the
+/// resolved target version: not installed, an explicit version pin that differs from what's
+/// installed (including a downgrade), or a channel-resolved version newer than what's
+/// installed.
+fn needs_update(tool: &Tool, current_version: Option<&Version>, target_version: &Version) -> bool {
+    match current_version {
+        None => true,
+        Some(ver) => {
+            ver != target_version && (tool.explicit_version.is_some() || ver < target_version)
+        }
+    }
+}
+
+/// Runs `exe_path --version` and extracts a [`Version`] from its output via `version_pattern`,
+/// returning `None` if the binary is missing, fails to run, or its output doesn't parse.
+fn detect_installed_version(exe_path: &Path, version_pattern: &str) -> Option<Version> {
+    if !exe_path.exists() {
+        return None;
+    }
+
+    let output = Command::new(exe_path).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let version_str = String::from_utf8_lossy(&output.stdout);
+    regex::Regex::new(version_pattern)
+        .ok()?
+        .captures(&version_str)?
+        .get(1)?
+        .as_str()
+        .parse::<Version>()
+        .ok()
+}
+
+fn process_tool(client: &Client, tool: &Tool, dir: &PathBuf, multi: &MultiProgress) -> Result<(), Box<dyn std::error::Error>> {
+    log(multi, format!("Checking {} installation...", tool.name));
+
+    // Check current version
+    let exe_path = dir.join(&tool.exe);
+    let current_version = detect_installed_version(&exe_path, &tool.version_pattern);
+
+    // Resolve the release to install: a pinned version, or the newest on the selected channel
+    let release = resolve_release(client, tool)?;
+    let target_version = Version::parse(release.tag_name.trim_start_matches('v'))?;
+
     // Check if update needed
-    if let Some(ver) = current_version {
-        println!("Installed version: {}", ver);
-        println!("Latest version: {}", latest_version);
-        if ver >= latest_version {
-            println!("{} is up to date!", tool.name);
+    if let Some(ver) = &current_version {
+        log(multi, format!("{}: installed {}, target {}", tool.name, ver, target_version));
+        if !needs_update(tool, Some(ver), &target_version) {
+            log(multi, format!("{} is up to date!", tool.name));
             return Ok(());
         }
-        println!("Update available!");
+        log(multi, format!("Update available for {}!", tool.name));
     } else {
-        println!("Not installed or version check failed.");
+        log(multi, format!("{} not installed or version check failed.", tool.name));
     }
 
     // Find and download asset
@@ -125,29 +316,34 @@ fn process_tool(client: &Client, tool: &Tool, dir: &PathBuf) -> Result<(), Box<d
         .find(|a| tool.matches_asset(&a.name))
         .ok_or("Compatible release not found")?;
 
-    let zip_path = dir.join(&asset.name);
-
-    // Download with progress
+    // Download with progress, keeping the archive in memory so nothing under `dir` is
+    // touched until it's verified
     let response = client.get(&asset.browser_download_url).send()?;
     let pb = create_progress_bar(
+        multi,
         response.content_length().unwrap_or(0),
         &format!("Downloading {}", tool.name)
     );
 
     let bytes = response.bytes()?;
-    std::fs::File::create(&zip_path)?.write_all(&bytes)?;
     pb.inc(bytes.len() as u64);
     pb.finish();
 
-    // Extract with progress
-    let pb = create_progress_bar(0, &format!("Extracting {}", tool.name));
-    let mut archive = zip::ZipArchive::new(std::fs::File::open(&zip_path)?)?;
+    // Verify integrity before touching anything under `dir`
+    verify_asset(client, tool, &release, asset, &bytes, multi)?;
+
+    // Extract into a staging directory (a sibling of `dir`, so promotion is a single
+    // rename) so a failed or interrupted extraction never leaves the live install in a
+    // half-written state
+    let staging = tempfile::tempdir_in(dir.parent().ok_or("tool directory has no parent")?)?;
+    let pb = create_progress_bar(multi, 0, &format!("Extracting {}", tool.name));
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&bytes[..]))?;
     pb.set_length(archive.len() as u64);
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
         let outpath = match file.enclosed_name() {
-            Some(path) => dir.join(path),
+            Some(path) => staging.path().join(path),
             None => continue,
         };
 
@@ -165,22 +361,22 @@ fn process_tool(client: &Client, tool: &Tool, dir: &PathBuf) -> Result<(), Box<d
     }
     pb.finish();
 
-    std::fs::remove_file(zip_path)?;
+    // Extraction and verification both succeeded: swap the staged install in, moving
+    // the previous one into its `<name>.backup` sibling so `--rollback` can restore it
+    install::promote(dir, &staging)?;
+
     Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
-    let base_dir = match args.output {
-        Some(dir) => dir,
+    let base_dir = match &args.output {
+        Some(dir) => dir.clone(),
         None => std::env::current_dir()?
     };
     println!("Output directory: {}", base_dir.display());
 
-    let pwsh_dir = base_dir.join("pwsh");
-    let uv_dir = base_dir.join("uv");
-    std::fs::create_dir_all(&pwsh_dir)?;
-    std::fs::create_dir_all(&uv_dir)?;
+    let config = config::Config::load(args.config.as_deref())?;
 
     let client = Client::builder()
         .default_headers({
@@ -190,12 +386,92 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
         .build()?;
 
-    process_tool(&client, &Tool::powershell(), &pwsh_dir)?;
-    process_tool(&client, &Tool::uv(), &uv_dir)?;
+    if matches!(args.command, Some(Commands::Info)) {
+        return info::run(&client, &config.tools, &base_dir, &args);
+    }
+
+    if args.rollback {
+        // Each tool's rollback is independent, so one missing backup shouldn't prevent
+        // the others from being restored.
+        let mut failures = 0;
+        for tool_config in &config.tools {
+            let dir = base_dir.join(&tool_config.dir);
+            match install::rollback(&dir) {
+                Ok(()) => println!("Rolled back {}", tool_config.name),
+                Err(e) => {
+                    failures += 1;
+                    println!("  {}: FAILED ({e})", tool_config.name);
+                }
+            }
+        }
+
+        if failures > 0 {
+            return Err(format!("{failures} of {} tool(s) failed to roll back", config.tools.len()).into());
+        }
+        return Ok(());
+    }
+
+    let multi = MultiProgress::new();
+
+    // Each tool's release is independent, so process them concurrently; a scoped thread
+    // per tool shares the client and progress display without needing 'static data.
+    let outcomes: Vec<(String, Result<PathBuf, String>)> = thread::scope(|scope| {
+        // Pair each handle with its tool's name up front, so a panicked thread can still
+        // be reported against the right tool instead of just vanishing from the summary.
+        let handles: Vec<_> = config.tools.iter().map(|tool_config| {
+            let client = &client;
+            let multi = &multi;
+            let base_dir = &base_dir;
+            let args = &args;
+            let name = tool_config.name.clone();
+
+            let handle = scope.spawn(move || -> Result<PathBuf, String> {
+                (|| -> Result<PathBuf, Box<dyn std::error::Error>> {
+                    let tool = build_tool(tool_config, args)?;
 
-    println!("\nAll tools are up to date!");
-    println!("PowerShell: {}", pwsh_dir.join("pwsh.exe").display());
-    println!("UV: {}", uv_dir.join("uv.exe").display());
+                    let dir = base_dir.join(&tool_config.dir);
+                    std::fs::create_dir_all(&dir)?;
+
+                    process_tool(client, &tool, &dir, multi)?;
+                    Ok(dir.join(&tool.exe))
+                })()
+                .map_err(|e| e.to_string())
+            });
+
+            (name, handle)
+        }).collect();
+
+        handles.into_iter().map(|(name, handle)| {
+            let result = match handle.join() {
+                Ok(result) => result,
+                Err(panic) => {
+                    let reason = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string());
+                    Err(format!("thread panicked: {reason}"))
+                }
+            };
+            (name, result)
+        }).collect()
+    });
+
+    println!("\nSummary:");
+    let mut failures = 0;
+    for (name, result) in &outcomes {
+        match result {
+            Ok(path) => println!("  {name}: ok ({})", path.display()),
+            Err(e) => {
+                failures += 1;
+                println!("  {name}: FAILED ({e})");
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{failures} of {} tool(s) failed to update", outcomes.len()).into());
+    }
 
     Ok(())
 }
\ No newline at end of file
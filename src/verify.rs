@@ -0,0 +1,124 @@
+use reqwest::blocking::Client;
+use sha2::{Digest, Sha256};
+
+/// Computes the lowercase hex SHA-256 digest of `bytes`.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Pulls the first 64-character hex run out of a checksum sidecar, tolerating
+/// the common `sha256sum`-style `<hex>  <filename>` format as well as a bare hex string.
+pub fn extract_hex_digest(contents: &str) -> Option<String> {
+    contents
+        .split_whitespace()
+        .find(|tok| tok.len() == 64 && tok.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|tok| tok.to_lowercase())
+}
+
+/// Best-effort extraction of a `<asset> <hex>` pair from a release body, used when a
+/// project (e.g. PowerShell) publishes checksums as release-notes text rather than a
+/// dedicated `.sha256` asset.
+pub fn find_hex_digest_in_body(body: &str, asset_name: &str) -> Option<String> {
+    body.lines()
+        .find(|line| line.contains(asset_name))
+        .and_then(extract_hex_digest)
+}
+
+/// Downloads `url` and returns its body as text, used for small sidecar files
+/// (checksums, signatures) rather than the archive itself.
+pub fn fetch_text(client: &Client, url: &str) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(client.get(url).send()?.text()?)
+}
+
+/// Verifies that `bytes` hashes to `expected_hex`, returning a descriptive error on mismatch.
+pub fn verify_checksum(bytes: &[u8], expected_hex: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let actual = sha256_hex(bytes);
+    if actual.eq_ignore_ascii_case(expected_hex) {
+        Ok(())
+    } else {
+        Err(format!(
+            "checksum mismatch: expected {}, got {}",
+            expected_hex, actual
+        )
+        .into())
+    }
+}
+
+/// Verifies a detached minisign signature over `bytes` using `public_key_b64`
+/// (the base64 key string as published by the tool's maintainers).
+pub fn verify_minisign(
+    bytes: &[u8],
+    signature_text: &str,
+    public_key_b64: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use minisign_verify::{PublicKey, Signature};
+
+    let public_key = PublicKey::from_base64(public_key_b64)?;
+    let signature = Signature::decode(signature_text)?;
+    public_key.verify(bytes, &signature, false)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_hex_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"hello world"),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    #[test]
+    fn extract_hex_digest_finds_sha256sum_style_line() {
+        let contents = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde  tool.zip\n";
+        assert_eq!(
+            extract_hex_digest(contents).as_deref(),
+            Some("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+        );
+    }
+
+    #[test]
+    fn extract_hex_digest_finds_bare_hex() {
+        let contents = "B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9";
+        assert_eq!(
+            extract_hex_digest(contents).as_deref(),
+            Some("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+        );
+    }
+
+    #[test]
+    fn extract_hex_digest_ignores_non_hex_tokens() {
+        assert_eq!(extract_hex_digest("no checksum here"), None);
+    }
+
+    #[test]
+    fn find_hex_digest_in_body_matches_line_by_asset_name() {
+        let body = "Release notes\n\
+                     tool-other.zip  1111111111111111111111111111111111111111111111111111111111111111\n\
+                     tool.zip  b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde\n";
+        assert_eq!(
+            find_hex_digest_in_body(body, "tool.zip").as_deref(),
+            Some("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9")
+        );
+    }
+
+    #[test]
+    fn verify_checksum_accepts_matching_case_insensitive_hex() {
+        let expected = "B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9";
+        assert!(verify_checksum(b"hello world", expected).is_ok());
+    }
+
+    #[test]
+    fn verify_checksum_rejects_mismatch() {
+        assert!(verify_checksum(b"hello world", "00").is_err());
+    }
+}